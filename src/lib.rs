@@ -1,4 +1,4 @@
-use std::{collections::HashMap, str::FromStr, hint::unreachable_unchecked};
+use std::{cell::Cell, collections::HashMap, str::FromStr};
 
 
 
@@ -7,12 +7,75 @@ pub enum JsonValue {
     Null,
     Bool(bool),
     Integer(i64),
+    U64(u64),
     Float(f64),
     String(String),
     Array(Vec<JsonValue>),
     Object(HashMap<String, JsonValue>),
 }
 
+/// The specific rule violated while parsing a JSON document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    ExpectedSomeValue,
+    ExpectedColon,
+    KeyMustBeAString,
+    TrailingCharacters,
+    TrailingComma,
+    EofWhileParsingString,
+    EofWhileParsingArray,
+    EofWhileParsingObject,
+    ControlCharacterInString,
+    InvalidEscape,
+    InvalidNumber,
+    InvalidUnicodeCodePoint,
+    UnexpectedEndOfHexEscape,
+    LoneLeadingSurrogateInHexEscape,
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            ErrorCode::ExpectedSomeValue => "expected some value",
+            ErrorCode::ExpectedColon => "expected `:`",
+            ErrorCode::KeyMustBeAString => "key must be a string",
+            ErrorCode::TrailingCharacters => "trailing characters",
+            ErrorCode::TrailingComma => "trailing comma",
+            ErrorCode::EofWhileParsingString => "EOF while parsing a string",
+            ErrorCode::EofWhileParsingArray => "EOF while parsing an array",
+            ErrorCode::EofWhileParsingObject => "EOF while parsing an object",
+            ErrorCode::ControlCharacterInString => {
+                "control character found while parsing a string"
+            }
+            ErrorCode::InvalidEscape => "invalid escape",
+            ErrorCode::InvalidNumber => "invalid number",
+            ErrorCode::InvalidUnicodeCodePoint => "invalid unicode code point",
+            ErrorCode::UnexpectedEndOfHexEscape => "unexpected end of hex escape",
+            ErrorCode::LoneLeadingSurrogateInHexEscape => {
+                "lone leading surrogate in hex escape"
+            }
+        };
+
+        write!(f, "{message}")
+    }
+}
+
+/// A parse failure with the line and column where it occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub code: ErrorCode,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at line {} column {}", self.code, self.line, self.col)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 impl JsonValue {
     pub fn as_bool(&self) -> Option<bool> {
         if let JsonValue::Bool(value) = self {
@@ -30,6 +93,14 @@ impl JsonValue {
         }
     }
 
+    pub fn as_u64(&self) -> Option<u64> {
+        if let JsonValue::U64(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+
     pub fn as_float(&self) -> Option<f64> {
         if let JsonValue::Float(value) = self {
             Some(*value)
@@ -113,146 +184,222 @@ impl JsonValue {
         (trimmed, unsafe { src.get_unchecked(0..src.len() - trimmed.len()) })
     }
 
-    pub fn parse_null(src: &str) -> (&str, Option<JsonValue>) {
-        let (src, sub_string) = Self::parse_sequence(src, "null");
-        (src, sub_string.map(|_| JsonValue::Null))
+    pub fn parse_null(src: &str) -> (&str, Result<JsonValue, ErrorCode>) {
+        let (new_src, sub_string) = Self::parse_sequence(src, "null");
+
+        match sub_string {
+            Some(_) => (new_src, Ok(JsonValue::Null)),
+            None => (src, Err(ErrorCode::ExpectedSomeValue)),
+        }
     }
 
-    pub fn parse_bool(src: &str) -> (&str, Option<JsonValue>) {
-        let (mut new_src, mut sub_string) = Self::parse_sequence(src, "true");
+    pub fn parse_bool(src: &str) -> (&str, Result<JsonValue, ErrorCode>) {
+        let (new_src, sub_string) = Self::parse_sequence(src, "true");
 
         if sub_string.is_some() {
-            return (new_src, Some(JsonValue::from(true)))
+            return (new_src, Ok(JsonValue::from(true)))
         }
 
-        (new_src, sub_string) = Self::parse_sequence(src, "false");
+        let (new_src, sub_string) = Self::parse_sequence(src, "false");
 
         if sub_string.is_some() {
-            return (new_src, Some(JsonValue::from(false)));
+            return (new_src, Ok(JsonValue::from(false)));
         }
 
-        (src, None)
+        (src, Err(ErrorCode::ExpectedSomeValue))
     }
 
-    pub fn parse_integer(src: &str) -> (&str, Option<JsonValue>) {
-        let (src, sub_string) = Self::parse_span(src, char::is_ascii_digit);
+    /// Parses the full JSON number grammar: an optional `-`, an integer
+    /// part (no leading zeros besides a bare `0`), an optional `.` +
+    /// digits fraction, and an optional `e`/`E` exponent with an optional
+    /// sign. Produces `Integer`/`U64` when there is no fraction/exponent,
+    /// otherwise `Float`; an integer literal that overflows both `i64` and
+    /// `u64` also becomes a `Float`.
+    pub fn parse_number(src: &str) -> (&str, Result<JsonValue, ErrorCode>) {
+        let mut new_src = src;
+
+        let negative = if let Some(rest) = new_src.strip_prefix('-') {
+            new_src = rest;
+            true
+        } else {
+            false
+        };
+
+        let (after_int, int_digits) = Self::parse_span(new_src, char::is_ascii_digit);
 
-        if sub_string.is_empty() {
-            return (src, None);
+        if int_digits.is_empty() {
+            return (new_src, Err(if negative {
+                ErrorCode::InvalidNumber
+            } else {
+                ErrorCode::ExpectedSomeValue
+            }));
         }
 
-        (
-            src,
-            sub_string
-                .parse::<i64>()
-                .ok()
-                .map(JsonValue::Integer)
-        )
-    }
+        if int_digits.len() > 1 && int_digits.starts_with('0') {
+            return (new_src, Err(ErrorCode::InvalidNumber));
+        }
 
-    pub fn parse_float(src: &str) -> (&str, Option<JsonValue>) {
-        let (mut new_src, whole_value) = Self::parse_integer(src);
+        new_src = after_int;
 
-        let (whole, has_whole) = match whole_value {
-            None => (0, false),
-            Some(JsonValue::Integer(value)) => (value, true),
-            _ => unsafe { unreachable_unchecked() },
-        };
+        let mut is_float = false;
+
+        if let Some(rest) = new_src.strip_prefix('.') {
+            let (after_frac, frac_digits) = Self::parse_span(rest, char::is_ascii_digit);
 
-        let point;
-        (new_src, point) = Self::parse_char(new_src, '.');
+            if frac_digits.is_empty() {
+                return (rest, Err(ErrorCode::InvalidNumber));
+            }
 
-        if point.is_none() {
-            return (src, None);
+            is_float = true;
+            new_src = after_frac;
         }
 
-        let frac_value;
-        (new_src, frac_value) = Self::parse_integer(new_src);
+        if let Some(rest) = new_src.strip_prefix(['e', 'E']) {
+            let rest = rest.strip_prefix(['+', '-']).unwrap_or(rest);
+            let (after_exp, exp_digits) = Self::parse_span(rest, char::is_ascii_digit);
 
-        let (frac, has_frac) = match frac_value {
-            None => (0, false),
-            Some(JsonValue::Integer(value)) => (value, true),
-            _ => unsafe { unreachable_unchecked() },
-        };
+            if exp_digits.is_empty() {
+                return (rest, Err(ErrorCode::InvalidNumber));
+            }
 
-        if !has_whole && !has_frac {
-            return (src, None);
+            is_float = true;
+            new_src = after_exp;
         }
 
-        let mut frac_part = frac as f64;
-        
-        while 1.0 < frac_part {
-            frac_part /= 10.0;
+        let number_str = &src[..src.len() - new_src.len()];
+
+        if is_float {
+            return match number_str.parse::<f64>() {
+                Ok(value) if value.is_finite() => (new_src, Ok(JsonValue::Float(value))),
+                _ => (src, Err(ErrorCode::InvalidNumber)),
+            };
+        }
+
+        if let Ok(value) = number_str.parse::<i64>() {
+            return (new_src, Ok(JsonValue::Integer(value)));
+        }
+
+        if !negative && let Ok(value) = number_str.parse::<u64>() {
+            return (new_src, Ok(JsonValue::U64(value)));
         }
 
-        (new_src, Some(JsonValue::from(whole as f64 + frac_part)))
+        // Overflows both i64 and u64 (a huge negative literal, or a
+        // positive literal beyond u64::MAX) — fall back to f64, same as
+        // the fractional/exponent path above.
+        match number_str.parse::<f64>() {
+            Ok(value) if value.is_finite() => (new_src, Ok(JsonValue::Float(value))),
+            _ => (src, Err(ErrorCode::InvalidNumber)),
+        }
     }
 
-    pub fn parse_string(src: &str) -> (&str, Option<JsonValue>) {
-        let (mut new_src, open_quote) = Self::parse_char(src, '"');
+    pub fn parse_string(src: &str) -> (&str, Result<JsonValue, ErrorCode>) {
+        let (new_src, open_quote) = Self::parse_char(src, '"');
 
         if open_quote.is_none() {
-            return (src, None);
+            return (src, Err(ErrorCode::ExpectedSomeValue));
         }
 
-        let mut string;
-        (new_src, string) = Self::parse_span(new_src, |&c| c != '"');
+        let mut result = String::new();
+        let mut chars = new_src.chars();
 
-        while string.ends_with('\\') {
-            let quote;
-            (new_src, quote) = Self::parse_char(new_src, '"');
+        loop {
+            let Some(c) = chars.next() else {
+                return (chars.as_str(), Err(ErrorCode::EofWhileParsingString));
+            };
 
-            if quote.is_none() {
-                return (src, None);
+            match c {
+                '"' => break,
+                c if (c as u32) < 0x20 => {
+                    return (chars.as_str(), Err(ErrorCode::ControlCharacterInString));
+                }
+                '\\' => match Self::parse_escape(&mut chars) {
+                    Ok(ch) => result.push(ch),
+                    Err(code) => return (chars.as_str(), Err(code)),
+                },
+                c => result.push(c),
             }
+        }
 
-            let tail;
-            (new_src, tail) = Self::parse_span(new_src, |&c| c != '"');
+        (chars.as_str(), Ok(JsonValue::from(result)))
+    }
 
-            // Safety:
-            // 
-            // - '"' is an ASCII character so it requres only one bytes
-            // - `src` contains only valid UTF-8
-            // - we parsed '"' so `src` contains '"'
-            // - we parsed `tail` so `src` contains `tail`
-            string = unsafe {
-                std::str::from_utf8_unchecked(
-                    std::slice::from_raw_parts(
-                        string.as_ptr(),
-                        string.len() + tail.len() + 1
-                    )
-                )
-            };
+    /// Decodes the escape sequence following a `\`, including `\uXXXX` and
+    /// surrogate pairs, per RFC 8259.
+    fn parse_escape(chars: &mut std::str::Chars) -> Result<char, ErrorCode> {
+        match chars.next().ok_or(ErrorCode::EofWhileParsingString)? {
+            '"' => Ok('"'),
+            '\\' => Ok('\\'),
+            '/' => Ok('/'),
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            'b' => Ok('\u{8}'),
+            'f' => Ok('\u{c}'),
+            'u' => {
+                let code_unit = Self::parse_hex4(chars)?;
+
+                let scalar = if (0xD800..=0xDBFF).contains(&code_unit) {
+                    if chars.next() != Some('\\') || chars.next() != Some('u') {
+                        return Err(ErrorCode::UnexpectedEndOfHexEscape);
+                    }
+
+                    let low = Self::parse_hex4(chars)?;
+
+                    if !(0xDC00..=0xDFFF).contains(&low) {
+                        return Err(ErrorCode::LoneLeadingSurrogateInHexEscape);
+                    }
+
+                    0x10000 + ((code_unit as u32 - 0xD800) << 10) + (low as u32 - 0xDC00)
+                } else if (0xDC00..=0xDFFF).contains(&code_unit) {
+                    return Err(ErrorCode::LoneLeadingSurrogateInHexEscape);
+                } else {
+                    code_unit as u32
+                };
+
+                char::from_u32(scalar).ok_or(ErrorCode::InvalidUnicodeCodePoint)
+            }
+            _ => Err(ErrorCode::InvalidEscape),
         }
+    }
 
-        let close_quote;
-        (new_src, close_quote) = Self::parse_char(new_src, '"');
+    /// Reads exactly four hex digits into a `u16` code unit.
+    fn parse_hex4(chars: &mut std::str::Chars) -> Result<u16, ErrorCode> {
+        let mut value: u16 = 0;
 
-        if close_quote.is_none() {
-            return (src, None);
+        for _ in 0..4 {
+            let digit = chars.next()
+                .ok_or(ErrorCode::EofWhileParsingString)?
+                .to_digit(16)
+                .ok_or(ErrorCode::InvalidEscape)?;
+
+            value = value * 16 + digit as u16;
         }
 
-        (new_src, Some(JsonValue::from(string)))
+        Ok(value)
     }
 
-    pub fn parse_array(src: &str) -> (&str, Option<JsonValue>) {
+    pub fn parse_array(src: &str) -> (&str, Result<JsonValue, ErrorCode>) {
         let (mut new_src, open_bracket) = Self::parse_char(src, '[');
 
         if open_bracket.is_none() {
-            return (src, None);
+            return (src, Err(ErrorCode::ExpectedSomeValue));
         }
 
-        (new_src, _) = Self::parse_span(new_src, |&c| char::is_whitespace(c));
+        (new_src, _) = Self::parse_whitespaces(new_src);
 
         let mut values = vec![];
 
+        if let (after_close, Some(_)) = Self::parse_char(new_src, ']') {
+            return (after_close, Ok(JsonValue::from(values)));
+        }
+
         loop {
             let value;
             (new_src, value) = Self::parse_value(new_src);
-            
+
             match value {
-                None => break,
-                Some(value) => values.push(value),
+                Ok(value) => values.push(value),
+                Err(code) => return (new_src, Err(code)),
             }
 
             (new_src, _) = Self::parse_whitespaces(new_src);
@@ -265,6 +412,10 @@ impl JsonValue {
             }
 
             (new_src, _) = Self::parse_whitespaces(new_src);
+
+            if Self::parse_char(new_src, ']').1.is_some() {
+                return (new_src, Err(ErrorCode::TrailingComma));
+            }
         }
 
         (new_src, _) = Self::parse_whitespaces(new_src);
@@ -273,28 +424,39 @@ impl JsonValue {
         (new_src, close_bracket) = Self::parse_char(new_src, ']');
 
         if close_bracket.is_none() {
-            return (src, None);
+            return (new_src, Err(ErrorCode::EofWhileParsingArray));
         }
 
-        (new_src, Some(JsonValue::from(values)))
+        (new_src, Ok(JsonValue::from(values)))
     }
 
-    pub fn parse_object(src: &str) -> (&str, Option<JsonValue>) {
+    pub fn parse_object(src: &str) -> (&str, Result<JsonValue, ErrorCode>) {
         let (mut new_src, open_brace) = Self::parse_char(src, '{');
 
         if open_brace.is_none() {
-            return (src, None);
+            return (src, Err(ErrorCode::ExpectedSomeValue));
         }
 
-        (new_src, _) = Self::parse_span(new_src, |&c| char::is_whitespace(c));
+        (new_src, _) = Self::parse_whitespaces(new_src);
 
         let mut values = HashMap::new();
 
+        if let (after_close, Some(_)) = Self::parse_char(new_src, '}') {
+            return (after_close, Ok(JsonValue::from(values)));
+        }
+
         loop {
             let key;
             (new_src, key) = Self::parse_string(new_src);
 
-            let Some(JsonValue::String(key)) = key else { break };
+            let key = match key {
+                Ok(JsonValue::String(key)) => key,
+                Ok(_) => unreachable!("parse_string only produces JsonValue::String"),
+                Err(ErrorCode::ExpectedSomeValue) => {
+                    return (new_src, Err(ErrorCode::KeyMustBeAString))
+                }
+                Err(code) => return (new_src, Err(code)),
+            };
 
             (new_src, _) = Self::parse_whitespaces(new_src);
 
@@ -302,16 +464,17 @@ impl JsonValue {
             (new_src, colon) = Self::parse_char(new_src, ':');
 
             if colon.is_none() {
-                return (src, None);
+                return (new_src, Err(ErrorCode::ExpectedColon));
             }
 
             (new_src, _) = Self::parse_whitespaces(new_src);
 
             let value;
             (new_src, value) = Self::parse_value(new_src);
-            
-            let Some(value) = value else {
-                return (src, None);
+
+            let value = match value {
+                Ok(value) => value,
+                Err(code) => return (new_src, Err(code)),
             };
 
             values.insert(key, value);
@@ -326,6 +489,10 @@ impl JsonValue {
             }
 
             (new_src, _) = Self::parse_whitespaces(new_src);
+
+            if Self::parse_char(new_src, '}').1.is_some() {
+                return (new_src, Err(ErrorCode::TrailingComma));
+            }
         }
 
         (new_src, _) = Self::parse_whitespaces(new_src);
@@ -334,18 +501,17 @@ impl JsonValue {
         (new_src, close_bracket) = Self::parse_char(new_src, '}');
 
         if close_bracket.is_none() {
-            return (src, None);
+            return (new_src, Err(ErrorCode::EofWhileParsingObject));
         }
 
-        (new_src, Some(JsonValue::from(values)))
+        (new_src, Ok(JsonValue::from(values)))
     }
 
-    pub fn parse_value(src: &str) -> (&str, Option<JsonValue>) {
+    pub fn parse_value(src: &str) -> (&str, Result<JsonValue, ErrorCode>) {
         Self::parse_try(src, [
             Self::parse_null,
             Self::parse_bool,
-            Self::parse_float,
-            Self::parse_integer,
+            Self::parse_number,
             Self::parse_string,
             Self::parse_array,
             Self::parse_object,
@@ -354,157 +520,1178 @@ impl JsonValue {
 
     pub fn parse_try(
         mut src: &str,
-        parsers: impl IntoIterator<Item = fn(&str) -> (&str, Option<JsonValue>)>
-    ) -> (&str, Option<JsonValue>) {
-        let mut value = None;
+        parsers: impl IntoIterator<Item = fn(&str) -> (&str, Result<JsonValue, ErrorCode>)>
+    ) -> (&str, Result<JsonValue, ErrorCode>) {
+        let mut result = Err(ErrorCode::ExpectedSomeValue);
 
         for parse in parsers.into_iter() {
-            (src, value) = parse(src);
+            (src, result) = parse(src);
 
-            if let Some(value) = value {
-                return (src, Some(value));
+            match result {
+                Ok(_) => return (src, result),
+                Err(ErrorCode::ExpectedSomeValue) => continue,
+                Err(_) => return (src, result),
             }
         }
 
-        (src, value)
+        (src, result)
     }
-}
 
-impl From<bool> for JsonValue {
-    fn from(value: bool) -> Self {
-        Self::Bool(value)
+    /// Converts a remainder-pointer offset into a `ParseError` with the
+    /// line/column where parsing stopped, by counting characters consumed
+    /// from the start of the original, untrimmed input.
+    fn locate_error(src: &str, remainder: &str, code: ErrorCode) -> ParseError {
+        let offset = remainder.as_ptr() as usize - src.as_ptr() as usize;
+        let consumed = &src[..offset];
+
+        let line = consumed.matches('\n').count() + 1;
+        let col = match consumed.rfind('\n') {
+            Some(index) => consumed[index + 1..].chars().count() + 1,
+            None => consumed.chars().count() + 1,
+        };
+
+        ParseError { code, line, col }
     }
 }
 
-impl From<i64> for JsonValue {
-    fn from(value: i64) -> Self {
-        Self::Integer(value)
+impl JsonValue {
+    /// Escapes a string per RFC 8259 and wraps it in double quotes.
+    fn escape_string(value: &str) -> String {
+        let mut output = String::with_capacity(value.len() + 2);
+        output.push('"');
+
+        for c in value.chars() {
+            match c {
+                '"' => output.push_str("\\\""),
+                '\\' => output.push_str("\\\\"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                '\u{8}' => output.push_str("\\b"),
+                '\u{c}' => output.push_str("\\f"),
+                c if (c as u32) < 0x20 => {
+                    output.push_str(&format!("\\u{:04x}", c as u32));
+                }
+                c => output.push(c),
+            }
+        }
+
+        output.push('"');
+        output
     }
-}
 
-impl From<f64> for JsonValue {
-    fn from(value: f64) -> Self {
-        Self::Float(value)
+    /// Formats a float so it always round-trips as a `Float`, appending
+    /// `.0` when Rust's own formatting would otherwise drop the point.
+    ///
+    /// `NaN` and `Infinity` have no JSON representation; since `parse_number`
+    /// never produces them, only a directly-constructed `JsonValue::Float`
+    /// can reach this, and we fall back to `null` rather than emit invalid
+    /// JSON.
+    fn format_float(value: f64) -> String {
+        if value.is_nan() || value.is_infinite() {
+            return "null".to_string();
+        }
+
+        let formatted = value.to_string();
+
+        if formatted.contains('.') || formatted.contains('e') || formatted.contains('E') {
+            formatted
+        } else {
+            formatted + ".0"
+        }
     }
-}
 
-impl From<String> for JsonValue {
-    fn from(value: String) -> Self {
-        Self::String(value)
+    /// Serializes `self` as pretty-printed JSON, indenting nested arrays
+    /// and objects by `indent` spaces per level.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut output = String::new();
+        self.write_pretty(&mut output, indent, 0);
+        output
     }
-}
 
-impl From<&'_ str> for JsonValue {
-    fn from(value: &str) -> Self {
-        Self::String(value.to_owned())
+    fn write_pretty(&self, output: &mut String, indent: usize, depth: usize) {
+        match self {
+            JsonValue::Array(values) if !values.is_empty() => {
+                output.push('[');
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+
+                    output.push('\n');
+                    output.push_str(&" ".repeat(indent * (depth + 1)));
+                    value.write_pretty(output, indent, depth + 1);
+                }
+
+                output.push('\n');
+                output.push_str(&" ".repeat(indent * depth));
+                output.push(']');
+            }
+            JsonValue::Object(values) if !values.is_empty() => {
+                output.push('{');
+
+                for (i, (key, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+
+                    output.push('\n');
+                    output.push_str(&" ".repeat(indent * (depth + 1)));
+                    output.push_str(&Self::escape_string(key));
+                    output.push_str(": ");
+                    value.write_pretty(output, indent, depth + 1);
+                }
+
+                output.push('\n');
+                output.push_str(&" ".repeat(indent * depth));
+                output.push('}');
+            }
+            JsonValue::Array(_) => output.push_str("[]"),
+            JsonValue::Object(_) => output.push_str("{}"),
+            _ => output.push_str(&self.to_string()),
+        }
     }
 }
 
-impl From<Vec<Self>> for JsonValue {
-    fn from(value: Vec<Self>) -> Self {
-        Self::Array(value)
+/// The specific rule violated while parsing a JSONPath expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathErrorCode {
+    ExpectedRoot,
+    UnexpectedToken,
+    UnterminatedBracket,
+    UnterminatedString,
+    InvalidIndex,
+    InvalidFilterExpression,
+}
+
+impl std::fmt::Display for PathErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            PathErrorCode::ExpectedRoot => "path must start with `$`",
+            PathErrorCode::UnexpectedToken => "unexpected token",
+            PathErrorCode::UnterminatedBracket => "unterminated `[`",
+            PathErrorCode::UnterminatedString => "unterminated string literal",
+            PathErrorCode::InvalidIndex => "invalid array index",
+            PathErrorCode::InvalidFilterExpression => "invalid filter expression",
+        };
+
+        write!(f, "{message}")
     }
 }
 
-impl From<HashMap<String, Self>> for JsonValue {
-    fn from(value: HashMap<String, Self>) -> Self {
-        Self::Object(value)
+/// A JSONPath parse failure with the character offset where it occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathError {
+    pub code: PathErrorCode,
+    pub pos: usize,
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at character {}", self.code, self.pos)
     }
 }
 
-impl FromStr for JsonValue {
-    type Err = String;
+impl std::error::Error for PathError {}
 
-    fn from_str(src: &str) -> Result<Self, Self::Err> {
-        let (src, value) = Self::parse_value(src.trim());
+#[derive(Clone, Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(i64),
+    Slice { start: Option<i64>, end: Option<i64>, step: i64 },
+    Wildcard,
+    RecursiveDescent,
+    Filter(FilterExpr),
+}
 
-        let Some(value) = value else {
-            return Err(format!("failed to parse \"{src}\""));
-        };
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
 
-        if !src.is_empty() {
-            return Err(
-                format!("failed to parse entire value, reminder: \"{src}\"")
-            );
-        }
+#[derive(Clone, Debug, PartialEq)]
+enum FilterLiteral {
+    Number(f64),
+    String(String),
+    Bool(bool),
+}
 
-        Ok(value)
-    }
+#[derive(Clone, Debug, PartialEq)]
+struct FilterExpr {
+    /// `None` for a bare `@` comparison against the node itself; `Some`
+    /// for `@.field` or `@.a.b`, split on `.` into the chain of keys to
+    /// walk from the node.
+    field: Option<Vec<String>>,
+    op: CompareOp,
+    value: FilterLiteral,
 }
 
+impl JsonValue {
+    /// Evaluates a JSONPath expression against `self`, supporting `$` root,
+    /// `.key` / `['key']` child access, `[n]` indexing (negative counts
+    /// from the end), `[start:end:step]` slices, `*` wildcards, `..`
+    /// recursive descent, and `[?(@.field OP literal)]` filters with
+    /// `== != < <= > >=`. `@` alone compares the node itself rather than
+    /// a field of it.
+    pub fn select(&self, path: &str) -> Result<Vec<&JsonValue>, PathError> {
+        let segments = Self::parse_path(path)?;
+        Ok(Self::eval_segments(&segments, vec![self]))
+    }
 
+    fn path_error(path: &str, remainder: &str, code: PathErrorCode) -> PathError {
+        let offset = remainder.as_ptr() as usize - path.as_ptr() as usize;
+        let pos = path[..offset].chars().count();
+        PathError { code, pos }
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse_path(path: &str) -> Result<Vec<PathSegment>, PathError> {
+        let Some(mut rest) = path.strip_prefix('$') else {
+            return Err(PathError { code: PathErrorCode::ExpectedRoot, pos: 0 });
+        };
 
-    #[test]
-    fn test_parse_json() {
-        let input = r#"{
-            "quiz": {
-                "sport": {
-                    "q1": {
-                        "question": "Which one is correct team name in NBA?",
-                        "options": [
-                            "New York Bulls",
-                            "Los Angeles Kings",
-                            "Golden State Warriros",
-                            "Huston Rocket"
-                        ],
-                        "answer": "Huston \"Rocket\""
-                    }
-                },
-                "maths": {
-                    "q1": {
-                        "question": "5 + 7 = ?",
-                        "options": [
-                            "10",
-                            "11",
-                            "12",
-                            "13"
-                        ],
-                        "answer": "12"
-                    },
-                    "q2": {
-                        "question": "12 - 8 = ?",
-                        "options": [
-                            "1",
-                            "2",
-                            "3",
-                            "4"
-                        ],
-                        "answer": "4"
+        let mut segments = Vec::new();
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("..") {
+                segments.push(PathSegment::RecursiveDescent);
+                rest = after;
+
+                if let Some(after_star) = rest.strip_prefix('*') {
+                    segments.push(PathSegment::Wildcard);
+                    rest = after_star;
+                } else if !rest.starts_with('.') && !rest.starts_with('[') {
+                    let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                        .unwrap_or(rest.len());
+
+                    if end > 0 {
+                        segments.push(PathSegment::Key(rest[..end].to_owned()));
+                        rest = &rest[end..];
                     }
                 }
+
+                continue;
             }
-        }"#;
 
-        let value: JsonValue = input.parse().unwrap();
+            if let Some(after) = rest.strip_prefix('.') {
+                if let Some(after_star) = after.strip_prefix('*') {
+                    segments.push(PathSegment::Wildcard);
+                    rest = after_star;
+                    continue;
+                }
 
-        println!("{value:#?}");
+                let end = after.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+
+                if end == 0 {
+                    return Err(Self::path_error(path, after, PathErrorCode::UnexpectedToken));
+                }
+
+                segments.push(PathSegment::Key(after[..end].to_owned()));
+                rest = &after[end..];
+                continue;
+            }
+
+            if let Some(after) = rest.strip_prefix('[') {
+                let Some(close) = Self::find_matching_bracket(after) else {
+                    return Err(Self::path_error(path, rest, PathErrorCode::UnterminatedBracket));
+                };
+
+                let content = &after[..close];
+                segments.push(Self::parse_bracket_content(path, content)?);
+                rest = &after[close + 1..];
+                continue;
+            }
+
+            return Err(Self::path_error(path, rest, PathErrorCode::UnexpectedToken));
+        }
+
+        Ok(segments)
     }
 
-    #[test]
-    fn test_parse_float() {
-        let input = 1324.34576.to_string();
+    /// Finds the `]` that closes the `[` already consumed from `after`,
+    /// skipping over quoted string literals and any nested `[...]` (e.g. the
+    /// `[0]` in `[?(@.tags[0]=='x')]`; the filter then looks up `tags[0]`
+    /// as a plain object key and does not index into an array).
+    /// Returns `None` if `after` never closes the bracket.
+    fn find_matching_bracket(after: &str) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut quote = None;
+
+        for (index, ch) in after.char_indices() {
+            if let Some(q) = quote {
+                if ch == q {
+                    quote = None;
+                }
+                continue;
+            }
 
-        let JsonValue::Float(value) = input.parse().unwrap() else {
-            panic!()
-        };
+            match ch {
+                '\'' | '"' => quote = Some(ch),
+                '[' => depth += 1,
+                ']' if depth > 0 => depth -= 1,
+                ']' => return Some(index),
+                _ => {}
+            }
+        }
 
-        assert_eq!(input, value.to_string());
+        None
     }
 
-    #[test]
-    fn test_parse_object() {
-        let input
-            = r#"{   "key"  :     true,  "key341": null  ,   "true" : 234  }"#;
-        
-        let JsonValue::Object(value) = input.parse().unwrap() else {
-            panic!()
+    fn parse_bracket_content(path: &str, content: &str) -> Result<PathSegment, PathError> {
+        if content == "*" {
+            return Ok(PathSegment::Wildcard);
+        }
+
+        if let Some(inner) = content.strip_prefix("?(").and_then(|s| s.strip_suffix(')')) {
+            return Ok(PathSegment::Filter(Self::parse_filter(path, inner)?));
+        }
+
+        if let Some(rest) = content.strip_prefix('\'') {
+            return match rest.strip_suffix('\'') {
+                Some(key) => Ok(PathSegment::Key(key.to_owned())),
+                None => Err(Self::path_error(path, content, PathErrorCode::UnterminatedString)),
+            };
+        }
+
+        if let Some(rest) = content.strip_prefix('"') {
+            return match rest.strip_suffix('"') {
+                Some(key) => Ok(PathSegment::Key(key.to_owned())),
+                None => Err(Self::path_error(path, content, PathErrorCode::UnterminatedString)),
+            };
+        }
+
+        if content.contains(':') {
+            let mut parts = content.splitn(3, ':');
+            let start = parts.next().unwrap_or("");
+            let end = parts.next().unwrap_or("");
+            let step = parts.next().unwrap_or("");
+
+            let parse_opt = |s: &str| -> Result<Option<i64>, PathError> {
+                if s.is_empty() {
+                    Ok(None)
+                } else {
+                    s.parse::<i64>()
+                        .map(Some)
+                        .map_err(|_| Self::path_error(path, content, PathErrorCode::InvalidIndex))
+                }
+            };
+
+            return Ok(PathSegment::Slice {
+                start: parse_opt(start)?,
+                end: parse_opt(end)?,
+                step: parse_opt(step)?.unwrap_or(1),
+            });
+        }
+
+        content.parse::<i64>()
+            .map(PathSegment::Index)
+            .map_err(|_| Self::path_error(path, content, PathErrorCode::InvalidIndex))
+    }
+
+    fn parse_filter(path: &str, inner: &str) -> Result<FilterExpr, PathError> {
+        let inner = inner.trim();
+
+        let Some(after_at) = inner.strip_prefix('@') else {
+            return Err(Self::path_error(path, inner, PathErrorCode::InvalidFilterExpression));
         };
 
-        println!("{value:?}");
+        let (field, rest) = if let Some(after_dot) = after_at.strip_prefix('.') {
+            let field_end = after_dot.find(|c: char| c.is_whitespace() || "=!<>".contains(c))
+                .unwrap_or(after_dot.len());
+
+            if field_end == 0 {
+                return Err(Self::path_error(path, after_dot, PathErrorCode::InvalidFilterExpression));
+            }
+
+            let field = &after_dot[..field_end];
+
+            if field.split('.').any(|part| part.is_empty()) {
+                return Err(Self::path_error(path, after_dot, PathErrorCode::InvalidFilterExpression));
+            }
+
+            let keys = field.split('.').map(str::to_owned).collect();
+
+            (Some(keys), after_dot[field_end..].trim_start())
+        } else {
+            (None, after_at.trim_start())
+        };
+
+        let (rest, op) = if let Some(r) = rest.strip_prefix("==") {
+            (r, CompareOp::Eq)
+        } else if let Some(r) = rest.strip_prefix("!=") {
+            (r, CompareOp::Ne)
+        } else if let Some(r) = rest.strip_prefix("<=") {
+            (r, CompareOp::Le)
+        } else if let Some(r) = rest.strip_prefix(">=") {
+            (r, CompareOp::Ge)
+        } else if let Some(r) = rest.strip_prefix('<') {
+            (r, CompareOp::Lt)
+        } else if let Some(r) = rest.strip_prefix('>') {
+            (r, CompareOp::Gt)
+        } else {
+            return Err(Self::path_error(path, rest, PathErrorCode::InvalidFilterExpression));
+        };
+
+        let rest = rest.trim();
+
+        let value = if let Some(literal) = rest.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+            FilterLiteral::String(literal.to_owned())
+        } else if let Some(literal) = rest.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            FilterLiteral::String(literal.to_owned())
+        } else if rest == "true" {
+            FilterLiteral::Bool(true)
+        } else if rest == "false" {
+            FilterLiteral::Bool(false)
+        } else if let Ok(number) = rest.parse::<f64>() {
+            FilterLiteral::Number(number)
+        } else {
+            return Err(Self::path_error(path, rest, PathErrorCode::InvalidFilterExpression));
+        };
+
+        Ok(FilterExpr { field, op, value })
+    }
+
+    fn eval_segments<'v>(segments: &[PathSegment], nodes: Vec<&'v JsonValue>) -> Vec<&'v JsonValue> {
+        let mut current = nodes;
+
+        for segment in segments {
+            current = match segment {
+                PathSegment::RecursiveDescent => {
+                    current.iter().flat_map(|node| Self::descendants(node)).collect()
+                }
+                _ => current.iter().flat_map(|node| Self::apply_segment(node, segment)).collect(),
+            };
+        }
+
+        current
+    }
+
+    fn descendants(node: &JsonValue) -> Vec<&JsonValue> {
+        let mut result = vec![node];
+
+        match node {
+            JsonValue::Array(values) => {
+                for value in values {
+                    result.extend(Self::descendants(value));
+                }
+            }
+            JsonValue::Object(values) => {
+                for value in values.values() {
+                    result.extend(Self::descendants(value));
+                }
+            }
+            _ => {}
+        }
+
+        result
+    }
+
+    fn apply_segment<'v>(node: &'v JsonValue, segment: &PathSegment) -> Vec<&'v JsonValue> {
+        match segment {
+            PathSegment::Key(key) => match node {
+                JsonValue::Object(map) => map.get(key).into_iter().collect(),
+                _ => vec![],
+            },
+            PathSegment::Index(index) => match node {
+                JsonValue::Array(values) => Self::index_into(values, *index).into_iter().collect(),
+                _ => vec![],
+            },
+            PathSegment::Slice { start, end, step } => match node {
+                JsonValue::Array(values) => Self::slice_into(values, *start, *end, *step),
+                _ => vec![],
+            },
+            PathSegment::Wildcard => match node {
+                JsonValue::Array(values) => values.iter().collect(),
+                JsonValue::Object(map) => map.values().collect(),
+                _ => vec![],
+            },
+            PathSegment::Filter(filter) => match node {
+                JsonValue::Array(values) => {
+                    values.iter().filter(|value| Self::matches_filter(value, filter)).collect()
+                }
+                JsonValue::Object(map) => {
+                    map.values().filter(|value| Self::matches_filter(value, filter)).collect()
+                }
+                other => {
+                    if Self::matches_filter(other, filter) {
+                        vec![other]
+                    } else {
+                        vec![]
+                    }
+                }
+            },
+            PathSegment::RecursiveDescent => unreachable!("handled in eval_segments"),
+        }
+    }
+
+    fn index_into(values: &[JsonValue], index: i64) -> Option<&JsonValue> {
+        let len = values.len() as i64;
+        let actual = if index < 0 { len + index } else { index };
+
+        if actual < 0 || actual >= len {
+            None
+        } else {
+            values.get(actual as usize)
+        }
+    }
+
+    fn slice_into(values: &[JsonValue], start: Option<i64>, end: Option<i64>, step: i64) -> Vec<&JsonValue> {
+        let len = values.len() as i64;
+
+        if step <= 0 || len == 0 {
+            return vec![];
+        }
+
+        let normalize = |i: i64| -> i64 {
+            if i < 0 { (len + i).max(0) } else { i.min(len) }
+        };
+
+        let start = normalize(start.unwrap_or(0));
+        let end = normalize(end.unwrap_or(len));
+
+        let mut result = vec![];
+        let mut i = start;
+
+        while i < end {
+            if let Some(value) = values.get(i as usize) {
+                result.push(value);
+            }
+
+            i += step;
+        }
+
+        result
+    }
+
+    fn matches_filter(node: &JsonValue, filter: &FilterExpr) -> bool {
+        let field_value = match &filter.field {
+            Some(keys) => {
+                let mut value = node;
+
+                for key in keys {
+                    let Some(next) = value.as_object().and_then(|map| map.get(key)) else {
+                        return false;
+                    };
+                    value = next;
+                }
+
+                value
+            }
+            None => node,
+        };
+
+        match (&filter.value, field_value) {
+            (FilterLiteral::Number(expected), JsonValue::Integer(actual)) => {
+                Self::compare_numbers(*actual as f64, filter.op, *expected)
+            }
+            (FilterLiteral::Number(expected), JsonValue::U64(actual)) => {
+                Self::compare_numbers(*actual as f64, filter.op, *expected)
+            }
+            (FilterLiteral::Number(expected), JsonValue::Float(actual)) => {
+                Self::compare_numbers(*actual, filter.op, *expected)
+            }
+            (FilterLiteral::String(expected), JsonValue::String(actual)) => {
+                Self::compare_ord(actual.as_str(), filter.op, expected.as_str())
+            }
+            (FilterLiteral::Bool(expected), JsonValue::Bool(actual)) => {
+                actual == expected && matches!(filter.op, CompareOp::Eq)
+                    || actual != expected && matches!(filter.op, CompareOp::Ne)
+            }
+            _ => false,
+        }
+    }
+
+    fn compare_numbers(actual: f64, op: CompareOp, expected: f64) -> bool {
+        match op {
+            CompareOp::Eq => actual == expected,
+            CompareOp::Ne => actual != expected,
+            CompareOp::Lt => actual < expected,
+            CompareOp::Le => actual <= expected,
+            CompareOp::Gt => actual > expected,
+            CompareOp::Ge => actual >= expected,
+        }
+    }
+
+    fn compare_ord<T: PartialOrd>(actual: T, op: CompareOp, expected: T) -> bool {
+        match op {
+            CompareOp::Eq => actual == expected,
+            CompareOp::Ne => actual != expected,
+            CompareOp::Lt => actual < expected,
+            CompareOp::Le => actual <= expected,
+            CompareOp::Gt => actual > expected,
+            CompareOp::Ge => actual >= expected,
+        }
+    }
+}
+
+impl std::fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{value}"),
+            JsonValue::Integer(value) => write!(f, "{value}"),
+            JsonValue::U64(value) => write!(f, "{value}"),
+            JsonValue::Float(value) => write!(f, "{}", Self::format_float(*value)),
+            JsonValue::String(value) => write!(f, "{}", Self::escape_string(value)),
+            JsonValue::Array(values) => {
+                write!(f, "[")?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, "{value}")?;
+                }
+
+                write!(f, "]")
+            }
+            JsonValue::Object(values) => {
+                write!(f, "{{")?;
+
+                for (i, (key, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, "{}:{value}", Self::escape_string(key))?;
+                }
+
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for JsonValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<u64> for JsonValue {
+    fn from(value: u64) -> Self {
+        Self::U64(value)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&'_ str> for JsonValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<Vec<Self>> for JsonValue {
+    fn from(value: Vec<Self>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl From<HashMap<String, Self>> for JsonValue {
+    fn from(value: HashMap<String, Self>) -> Self {
+        Self::Object(value)
+    }
+}
+
+impl FromStr for JsonValue {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let (remainder, value) = Self::parse_value(src.trim());
+
+        let value = value.map_err(|code| Self::locate_error(src, remainder, code))?;
+
+        let (remainder, _) = Self::parse_whitespaces(remainder);
+
+        if !remainder.is_empty() {
+            return Err(Self::locate_error(src, remainder, ErrorCode::TrailingCharacters));
+        }
+
+        Ok(value)
+    }
+}
+
+/// A borrowed, offset-tracked cursor for pull-based JSON parsing. Reuses
+/// the existing `parse_*` primitives but only materializes one value at a
+/// time, so a multi-megabyte top-level array/object can be streamed
+/// without building the whole `Vec`/`HashMap` up front.
+pub struct JsonCursor<'a> {
+    src: &'a str,
+    offset: Cell<usize>,
+}
+
+impl<'a> JsonCursor<'a> {
+    pub fn new(src: &'a str) -> Self {
+        JsonCursor { src, offset: Cell::new(0) }
+    }
+
+    fn remaining(&self) -> &'a str {
+        &self.src[self.offset.get()..]
+    }
+
+    fn advance_to(&self, rest: &'a str) {
+        self.offset.set(self.src.len() - rest.len());
+    }
+
+    fn skip_whitespace(&self) {
+        let (rest, _) = JsonValue::parse_whitespaces(self.remaining());
+        self.advance_to(rest);
+    }
+
+    /// Parses a string at the current offset, advancing past it on success.
+    pub fn string(&self) -> Option<String> {
+        self.skip_whitespace();
+        let (rest, result) = JsonValue::parse_string(self.remaining());
+
+        let JsonValue::String(value) = result.ok()? else {
+            return None;
+        };
+
+        self.advance_to(rest);
+        Some(value)
+    }
+
+    /// Parses a number at the current offset, advancing past it on success.
+    pub fn number(&self) -> Option<JsonValue> {
+        self.skip_whitespace();
+        let (rest, result) = JsonValue::parse_number(self.remaining());
+        let value = result.ok()?;
+        self.advance_to(rest);
+        Some(value)
+    }
+
+    /// Parses `true`/`false` at the current offset, advancing past it on success.
+    pub fn boolean(&self) -> Option<bool> {
+        self.skip_whitespace();
+        let (rest, result) = JsonValue::parse_bool(self.remaining());
+
+        let JsonValue::Bool(value) = result.ok()? else {
+            return None;
+        };
+
+        self.advance_to(rest);
+        Some(value)
+    }
+
+    /// Opens an array at the current offset and returns a lazy iterator
+    /// over its elements, parsing one at a time as the caller advances it.
+    pub fn array(&self) -> Option<JsonArrayIter<'_, 'a>> {
+        self.skip_whitespace();
+        let (rest, open) = JsonValue::parse_char(self.remaining(), '[');
+        open?;
+        self.advance_to(rest);
+
+        Some(JsonArrayIter { cursor: self, started: false, done: false })
+    }
+
+    /// Opens an object at the current offset and returns a lazy iterator
+    /// over its `(key, value)` entries, parsing one at a time.
+    pub fn object(&self) -> Option<JsonObjectIter<'_, 'a>> {
+        self.skip_whitespace();
+        let (rest, open) = JsonValue::parse_char(self.remaining(), '{');
+        open?;
+        self.advance_to(rest);
+
+        Some(JsonObjectIter { cursor: self, started: false, done: false })
+    }
+}
+
+/// Lazy iterator over an array's elements, produced by [`JsonCursor::array`].
+///
+/// Yields `Err` instead of ending the iteration early when the stream is
+/// malformed (a trailing comma) or truncated (missing closing `]`), so a
+/// caller can tell a genuine end-of-array apart from a broken one.
+pub struct JsonArrayIter<'c, 'a> {
+    cursor: &'c JsonCursor<'a>,
+    started: bool,
+    done: bool,
+}
+
+impl Iterator for JsonArrayIter<'_, '_> {
+    type Item = Result<JsonValue, ErrorCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.cursor.skip_whitespace();
+
+        if self.started {
+            let (rest, comma) = JsonValue::parse_char(self.cursor.remaining(), ',');
+
+            if comma.is_none() {
+                self.done = true;
+                let (rest, close) = JsonValue::parse_char(self.cursor.remaining(), ']');
+                self.cursor.advance_to(rest);
+                return match close {
+                    Some(_) => None,
+                    None => Some(Err(ErrorCode::EofWhileParsingArray)),
+                };
+            }
+
+            self.cursor.advance_to(rest);
+            self.cursor.skip_whitespace();
+
+            if JsonValue::parse_char(self.cursor.remaining(), ']').1.is_some() {
+                self.done = true;
+                return Some(Err(ErrorCode::TrailingComma));
+            }
+        }
+
+        let (rest, close) = JsonValue::parse_char(self.cursor.remaining(), ']');
+
+        if close.is_some() {
+            self.done = true;
+            self.cursor.advance_to(rest);
+            return None;
+        }
+
+        let (rest, value) = JsonValue::parse_value(self.cursor.remaining());
+
+        match value {
+            Ok(value) => {
+                self.cursor.advance_to(rest);
+                self.started = true;
+                Some(Ok(value))
+            }
+            Err(code) => {
+                self.done = true;
+                Some(Err(code))
+            }
+        }
+    }
+}
+
+/// Lazy iterator over an object's `(key, value)` entries, produced by
+/// [`JsonCursor::object`].
+///
+/// Yields `Err` instead of ending the iteration early when the stream is
+/// malformed (a trailing comma, a non-string key, a missing `:`) or
+/// truncated (missing closing `}`), so a caller can tell a genuine end of
+/// object apart from a broken one.
+pub struct JsonObjectIter<'c, 'a> {
+    cursor: &'c JsonCursor<'a>,
+    started: bool,
+    done: bool,
+}
+
+impl Iterator for JsonObjectIter<'_, '_> {
+    type Item = Result<(String, JsonValue), ErrorCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.cursor.skip_whitespace();
+
+        if self.started {
+            let (rest, comma) = JsonValue::parse_char(self.cursor.remaining(), ',');
+
+            if comma.is_none() {
+                self.done = true;
+                let (rest, close) = JsonValue::parse_char(self.cursor.remaining(), '}');
+                self.cursor.advance_to(rest);
+                return match close {
+                    Some(_) => None,
+                    None => Some(Err(ErrorCode::EofWhileParsingObject)),
+                };
+            }
+
+            self.cursor.advance_to(rest);
+            self.cursor.skip_whitespace();
+
+            if JsonValue::parse_char(self.cursor.remaining(), '}').1.is_some() {
+                self.done = true;
+                return Some(Err(ErrorCode::TrailingComma));
+            }
+        }
+
+        let (rest, close) = JsonValue::parse_char(self.cursor.remaining(), '}');
+
+        if close.is_some() {
+            self.done = true;
+            self.cursor.advance_to(rest);
+            return None;
+        }
+
+        let (rest, key) = JsonValue::parse_string(self.cursor.remaining());
+
+        let key = match key {
+            Ok(JsonValue::String(key)) => key,
+            Ok(_) => unreachable!("parse_string only produces JsonValue::String"),
+            Err(ErrorCode::ExpectedSomeValue) => {
+                self.done = true;
+                return Some(Err(ErrorCode::KeyMustBeAString));
+            }
+            Err(code) => {
+                self.done = true;
+                return Some(Err(code));
+            }
+        };
+
+        self.cursor.advance_to(rest);
+        self.cursor.skip_whitespace();
+
+        let (rest, colon) = JsonValue::parse_char(self.cursor.remaining(), ':');
+
+        if colon.is_none() {
+            self.done = true;
+            return Some(Err(ErrorCode::ExpectedColon));
+        }
+
+        self.cursor.advance_to(rest);
+        self.cursor.skip_whitespace();
+
+        let (rest, value) = JsonValue::parse_value(self.cursor.remaining());
+
+        match value {
+            Ok(value) => {
+                self.cursor.advance_to(rest);
+                self.started = true;
+                Some(Ok((key, value)))
+            }
+            Err(code) => {
+                self.done = true;
+                Some(Err(code))
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        let input = r#"{
+            "quiz": {
+                "sport": {
+                    "q1": {
+                        "question": "Which one is correct team name in NBA?",
+                        "options": [
+                            "New York Bulls",
+                            "Los Angeles Kings",
+                            "Golden State Warriros",
+                            "Huston Rocket"
+                        ],
+                        "answer": "Huston \"Rocket\""
+                    }
+                },
+                "maths": {
+                    "q1": {
+                        "question": "5 + 7 = ?",
+                        "options": [
+                            "10",
+                            "11",
+                            "12",
+                            "13"
+                        ],
+                        "answer": "12"
+                    },
+                    "q2": {
+                        "question": "12 - 8 = ?",
+                        "options": [
+                            "1",
+                            "2",
+                            "3",
+                            "4"
+                        ],
+                        "answer": "4"
+                    }
+                }
+            }
+        }"#;
+
+        let value: JsonValue = input.parse().unwrap();
+
+        println!("{value:#?}");
+    }
+
+    #[test]
+    fn test_parse_float() {
+        let input = 1324.34576.to_string();
+
+        let JsonValue::Float(value) = input.parse().unwrap() else {
+            panic!()
+        };
+
+        assert_eq!(input, value.to_string());
+    }
+
+    #[test]
+    fn test_parse_number_exponent() {
+        assert_eq!("1e10".parse(), Ok(JsonValue::Float(1e10)));
+        assert_eq!("-3.5".parse(), Ok(JsonValue::Float(-3.5)));
+        assert_eq!("2.5E-3".parse(), Ok(JsonValue::Float(2.5E-3)));
+    }
+
+    #[test]
+    fn test_parse_number_rejects_leading_zero() {
+        assert!("012".parse::<JsonValue>().is_err());
+    }
+
+    #[test]
+    fn test_parse_number_u64_overflow() {
+        let input = (i64::MAX as u64 + 1).to_string();
+
+        assert_eq!(input.parse(), Ok(JsonValue::U64(i64::MAX as u64 + 1)));
+    }
+
+    #[test]
+    fn test_parse_number_falls_back_to_float_on_integer_overflow() {
+        assert_eq!(
+            "-99999999999999999999999999".parse(),
+            Ok(JsonValue::Float(-99999999999999999999999999.0))
+        );
+        assert_eq!(
+            "999999999999999999999999999999".parse(),
+            Ok(JsonValue::Float(999999999999999999999999999999.0))
+        );
+    }
+
+    #[test]
+    fn test_parse_number_rejects_infinite_magnitude() {
+        assert_eq!(
+            "1e1000".parse::<JsonValue>().unwrap_err().code,
+            ErrorCode::InvalidNumber
+        );
+        assert_eq!(
+            "-1e1000".parse::<JsonValue>().unwrap_err().code,
+            ErrorCode::InvalidNumber
+        );
+    }
+
+    #[test]
+    fn test_parse_error_reports_line_and_column() {
+        let input = "{\n    \"key\" true\n}";
+
+        let error = input.parse::<JsonValue>().unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::ExpectedColon);
+        assert_eq!(error.line, 2);
+        assert_eq!(error.col, 11);
+    }
+
+    #[test]
+    fn test_parse_error_trailing_comma() {
+        let error = "[1, 2,]".parse::<JsonValue>().unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::TrailingComma);
+    }
+
+    #[test]
+    fn test_parse_error_key_must_be_a_string() {
+        let error = "{true: 1}".parse::<JsonValue>().unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::KeyMustBeAString);
+    }
+
+    #[test]
+    fn test_parse_error_object_key_propagates_string_errors() {
+        let error = r#"{"a\x":1}"#.parse::<JsonValue>().unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::InvalidEscape);
+
+        let error = r#"{"a"#.parse::<JsonValue>().unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::EofWhileParsingString);
+    }
+
+    #[test]
+    fn test_parse_object() {
+        let input
+            = r#"{   "key"  :     true,  "key341": null  ,   "true" : 234  }"#;
+        
+        let JsonValue::Object(value) = input.parse().unwrap() else {
+            panic!()
+        };
+
+        println!("{value:?}");
+    }
+
+    #[test]
+    fn test_parse_string_escapes() {
+        let input = r#""line\nbreak\ttab\\backslash\"quote""#;
+
+        let JsonValue::String(value) = input.parse().unwrap() else {
+            panic!()
+        };
+
+        assert_eq!(value, "line\nbreak\ttab\\backslash\"quote");
+    }
+
+    #[test]
+    fn test_parse_string_surrogate_pair() {
+        let input = r#""\ud83d\ude00""#;
+
+        let JsonValue::String(value) = input.parse().unwrap() else {
+            panic!()
+        };
+
+        assert_eq!(value, "\u{1F600}");
+    }
+
+    #[test]
+    fn test_parse_string_lone_surrogate_is_error() {
+        let input = r#""\ud83d""#;
+
+        assert!(input.parse::<JsonValue>().is_err());
+    }
+
+    #[test]
+    fn test_to_string_round_trip() {
+        let input = r#"{"key":true,"key341":null,"true":234}"#;
+
+        let value: JsonValue = input.parse().unwrap();
+        let round_tripped: JsonValue = value.to_string().parse().unwrap();
+
+        assert_eq!(value, round_tripped);
+    }
+
+    #[test]
+    fn test_to_string_non_finite_float_as_null() {
+        assert_eq!(JsonValue::Float(f64::INFINITY).to_string(), "null");
+        assert_eq!(JsonValue::Float(f64::NEG_INFINITY).to_string(), "null");
+        assert_eq!(JsonValue::Float(f64::NAN).to_string(), "null");
+    }
+
+    #[test]
+    fn test_to_string_pretty() {
+        let value = JsonValue::from(vec![
+            JsonValue::from(1_i64),
+            JsonValue::from(2_i64),
+        ]);
+
+        assert_eq!(value.to_string_pretty(2), "[\n  1,\n  2\n]");
     }
 
     #[test]
@@ -561,4 +1748,220 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_select_filter() {
+        let input = r#"[
+            {"name": "Jonson", "age": 19},
+            {"name": "Mary", "age": 13},
+            {"name": "Max", "age": 21}
+        ]"#;
+
+        let json: JsonValue = input.parse().unwrap();
+        let adults = json.select("$[?(@.age >= 18)]").unwrap();
+
+        let names: Vec<&str> = adults.iter()
+            .filter_map(|value| value.as_object())
+            .filter_map(|object| object.get("name"))
+            .filter_map(JsonValue::as_string)
+            .collect();
+
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"Jonson"));
+        assert!(names.contains(&"Max"));
+    }
+
+    #[test]
+    fn test_select_filter_bare_node() {
+        let json: JsonValue = "[1, 5, 10, 20]".parse().unwrap();
+        let result = json.select("$[?(@ >= 10)]").unwrap();
+
+        assert_eq!(result, vec![&JsonValue::Integer(10), &JsonValue::Integer(20)]);
+    }
+
+    #[test]
+    fn test_select_filter_with_nested_field() {
+        let input = r#"[{"address": {"city": "NYC"}}, {"address": {"city": "LA"}}]"#;
+        let json: JsonValue = input.parse().unwrap();
+
+        let result = json.select("$[?(@.address.city == 'NYC')]").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].select("$.address.city").unwrap(),
+            vec![&JsonValue::from("NYC")]
+        );
+    }
+
+    #[test]
+    fn test_select_filter_rejects_empty_nested_field_segment() {
+        let json = JsonValue::Null;
+        assert_eq!(
+            json.select("$[?(@.address..city == 'NYC')]").unwrap_err().code,
+            PathErrorCode::InvalidFilterExpression
+        );
+    }
+
+    #[test]
+    fn test_select_filter_with_nested_bracket_does_not_truncate() {
+        let input = r#"[{"tags": ["a", "b"]}, {"tags": ["x", "y"]}]"#;
+
+        let json: JsonValue = input.parse().unwrap();
+
+        // The filter's own `[0]` must not be mistaken for the end of the
+        // outer `[?(...)]` bracket.
+        let result = json.select("$[?(@.tags[0]=='x')]");
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_select_key_and_index() {
+        let input = r#"{"items": ["a", "b", "c"]}"#;
+        let json: JsonValue = input.parse().unwrap();
+
+        let first = json.select("$.items[0]").unwrap();
+        assert_eq!(first, vec![&JsonValue::from("a")]);
+
+        let last = json.select("$.items[-1]").unwrap();
+        assert_eq!(last, vec![&JsonValue::from("c")]);
+    }
+
+    #[test]
+    fn test_select_slice_and_wildcard() {
+        let input = r#"[1, 2, 3, 4, 5]"#;
+        let json: JsonValue = input.parse().unwrap();
+
+        let slice = json.select("$[1:3]").unwrap();
+        assert_eq!(slice, vec![&JsonValue::Integer(2), &JsonValue::Integer(3)]);
+
+        let all = json.select("$[*]").unwrap();
+        assert_eq!(all.len(), 5);
+    }
+
+    #[test]
+    fn test_select_recursive_descent() {
+        let input = r#"{"a": {"age": 1}, "b": {"age": 2}}"#;
+        let json: JsonValue = input.parse().unwrap();
+
+        let mut ages: Vec<i64> = json.select("$..age").unwrap()
+            .into_iter()
+            .filter_map(JsonValue::as_integer)
+            .collect();
+
+        ages.sort_unstable();
+        assert_eq!(ages, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_select_error_pos_counts_chars_not_bytes() {
+        let json = JsonValue::Null;
+        let err = json.select("$.héllo!bad").unwrap_err();
+
+        assert_eq!(err.code, PathErrorCode::UnexpectedToken);
+        assert_eq!(err.pos, 7);
+    }
+
+    #[test]
+    fn test_select_rejects_missing_root() {
+        let json = JsonValue::Null;
+        assert_eq!(
+            json.select("items[0]").unwrap_err().code,
+            PathErrorCode::ExpectedRoot
+        );
+    }
+
+    #[test]
+    fn test_cursor_array_is_lazy() {
+        let cursor = JsonCursor::new(r#"[1, 2, "three", true]"#);
+        let values: Vec<JsonValue> = cursor.array().unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(values, vec![
+            JsonValue::Integer(1),
+            JsonValue::Integer(2),
+            JsonValue::from("three"),
+            JsonValue::from(true),
+        ]);
+    }
+
+    #[test]
+    fn test_cursor_array_advances_shared_offset() {
+        let cursor = JsonCursor::new(r#"[1, 2] "tail""#);
+
+        let count = cursor.array().unwrap().count();
+        assert_eq!(count, 2);
+
+        assert_eq!(cursor.string().as_deref(), Some("tail"));
+    }
+
+    #[test]
+    fn test_cursor_object_entries() {
+        let cursor = JsonCursor::new(r#"{"a": 1, "b": 2}"#);
+        let entries: Vec<(String, JsonValue)> = cursor.object().unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(entries, vec![
+            ("a".to_owned(), JsonValue::Integer(1)),
+            ("b".to_owned(), JsonValue::Integer(2)),
+        ]);
+    }
+
+    #[test]
+    fn test_cursor_empty_array() {
+        let cursor = JsonCursor::new("[]");
+        assert_eq!(cursor.array().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_cursor_array_rejects_trailing_comma() {
+        let cursor = JsonCursor::new("[1, 2,]");
+        let mut iter = cursor.array().unwrap();
+
+        assert_eq!(iter.next(), Some(Ok(JsonValue::Integer(1))));
+        assert_eq!(iter.next(), Some(Ok(JsonValue::Integer(2))));
+        assert_eq!(iter.next(), Some(Err(ErrorCode::TrailingComma)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_cursor_array_reports_truncated_input() {
+        let cursor = JsonCursor::new("[1, 2");
+        let mut iter = cursor.array().unwrap();
+
+        assert_eq!(iter.next(), Some(Ok(JsonValue::Integer(1))));
+        assert_eq!(iter.next(), Some(Ok(JsonValue::Integer(2))));
+        assert_eq!(iter.next(), Some(Err(ErrorCode::EofWhileParsingArray)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_cursor_object_rejects_trailing_comma() {
+        let cursor = JsonCursor::new(r#"{"a": 1,}"#);
+        let mut iter = cursor.object().unwrap();
+
+        assert_eq!(iter.next(), Some(Ok(("a".to_owned(), JsonValue::Integer(1)))));
+        assert_eq!(iter.next(), Some(Err(ErrorCode::TrailingComma)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_cursor_object_reports_truncated_input() {
+        let cursor = JsonCursor::new(r#"{"a": 1"#);
+        let mut iter = cursor.object().unwrap();
+
+        assert_eq!(iter.next(), Some(Ok(("a".to_owned(), JsonValue::Integer(1)))));
+        assert_eq!(iter.next(), Some(Err(ErrorCode::EofWhileParsingObject)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_cursor_object_propagates_key_string_errors() {
+        let cursor = JsonCursor::new(r#"{"a\x": 1}"#);
+        let mut iter = cursor.object().unwrap();
+
+        assert_eq!(iter.next(), Some(Err(ErrorCode::InvalidEscape)));
+        assert_eq!(iter.next(), None);
+    }
 }
\ No newline at end of file